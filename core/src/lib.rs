@@ -1,8 +1,12 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use geojson::{Value, Geometry, GeoJson, Feature, FeatureCollection};
+use geo::{Contains, Intersects};
+use geo_types::{Coord, Geometry as GeoGeometry, LineString, MultiPolygon, Point, Polygon as GeoPolygon, Rect};
+use geojson::{feature::Id, Value, Geometry, GeoJson, Feature, FeatureCollection};
+use h3o::geom::{Geometry as H3Geometry, PolyfillConfig, ToCells};
 use h3o::{LatLng, Resolution, CellIndex};
 use rand::{rngs::StdRng, Rng, distributions::{Distribution, Uniform}, SeedableRng};
+use serde_json::{Map, Value as JsonValue};
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
@@ -18,40 +22,243 @@ pub enum CellState {
     Alive = 1,
 }
 
+/**
+ * Which `GeoSampler` `Universe::new` should seed initial cells with.
+ */
+#[wasm_bindgen]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SamplerKind {
+    Uniform = 0,
+    Cluster = 1,
+    Mask = 2,
+}
+
+/**
+ * A birth/survival ruleset for the hexagonal cellular automaton, e.g. the default `B2/S23`
+ * (birth on exactly 2 live neighbors, survival on 2 or 3). Callers may supply arbitrary rules
+ * to explore the wider family of hexagonal life-like automata.
+ */
+pub struct RuleSet {
+    pub birth: Vec<u8>,
+    pub survival: Vec<u8>,
+}
+
+impl Default for RuleSet {
+    fn default() -> Self {
+        RuleSet {
+            birth: vec![2],
+            survival: vec![2, 3],
+        }
+    }
+}
+
+// Average hexagon edge length in kilometers per H3 resolution (the published H3 resolution
+// table), indexed by resolution. Used to size a viewport's `grid_disk` search radius directly
+// from its angular extent rather than growing the radius ring by ring against an arbitrary cap —
+// at fine resolutions (e.g. res 9, ~0.17 km edge) a regional viewport a few hundred kilometers
+// across requires over a thousand rings, so any fixed cap either under-covers real viewports or is
+// too loose to bound anything.
+const AVERAGE_HEXAGON_EDGE_LENGTH_KM: [f64; 16] = [
+    1107.712591, 418.676005, 158.244656, 59.810858, 22.606379, 8.544408,
+    3.229483, 1.220630, 0.461355, 0.174376, 0.065908, 0.024911,
+    0.009416, 0.003560, 0.001349, 0.000510,
+];
+
+const KM_PER_DEGREE_LAT: f64 = 111.32;
+const EARTH_CIRCUMFERENCE_KM: f64 = 40_075.0;
+
+/**
+ * A lat/lng bounding box used to restrict `tick`/`render` to the cells currently on screen.
+ *
+ * Does not handle antimeridian-spanning boxes: `min_lng`/`max_lng` are assumed such that
+ * `min_lng < max_lng`. A Pacific-centered viewport passed as e.g. `min_lng=170, max_lng=-170`
+ * is not normalized and selects the complementary ~340° region instead of the dateline wedge.
+ */
+struct Viewport {
+    min_lat: f64,
+    min_lng: f64,
+    max_lat: f64,
+    max_lng: f64,
+}
+
+impl Viewport {
+    fn intersects_cell(&self, index: &CellIndex) -> bool {
+        let rect = Rect::new(
+            Coord { x: self.min_lng, y: self.min_lat },
+            Coord { x: self.max_lng, y: self.max_lat },
+        );
+        let boundary = index
+            .boundary()
+            .iter()
+            .map(|v| Coord { x: v.lng(), y: v.lat() })
+            .collect::<Vec<_>>();
+        let polygon = GeoPolygon::new(LineString::new(boundary), vec![]);
+        rect.intersects(&polygon)
+    }
+
+    fn center(&self) -> LatLng {
+        let lat = (self.min_lat + self.max_lat) / 2.0;
+        let lng = (self.min_lng + self.max_lng) / 2.0;
+        unsafe { LatLng::new(lat, lng).unwrap_unchecked() }
+    }
+
+    /**
+     * The `grid_disk` radius (in rings) needed for a center-outward search at `resolution` to
+     * reach every corner of this viewport, derived from the viewport's half-diagonal in
+     * kilometers rather than probed ring by ring. Longitude degrees are scaled by the cosine of
+     * the viewport's latitude since a degree of longitude shrinks away from the equator. Adds one
+     * ring of margin so cells just outside the box (needed by `intersects_cell` callers) are
+     * still reached, and clamps to half the Earth's circumference worth of rings so a
+     * degenerate/whole-globe viewport still terminates.
+     */
+    fn search_radius(&self, resolution: Resolution) -> u32 {
+        let center_lat_rad = ((self.min_lat + self.max_lat) / 2.0).to_radians();
+        let lat_km = (self.max_lat - self.min_lat) * KM_PER_DEGREE_LAT;
+        let lng_km = (self.max_lng - self.min_lng) * KM_PER_DEGREE_LAT * center_lat_rad.cos();
+        let half_diagonal_km = (lat_km.powi(2) + lng_km.powi(2)).sqrt() / 2.0;
+
+        let edge_km = AVERAGE_HEXAGON_EDGE_LENGTH_KM[usize::from(u8::from(resolution))];
+        let rings = (half_diagonal_km / edge_km).ceil() as u32 + 1;
+
+        let max_rings = (EARTH_CIRCUMFERENCE_KM / 2.0 / edge_km).ceil() as u32;
+        rings.min(max_rings)
+    }
+}
+
 #[wasm_bindgen]
 pub struct Universe {
     cells: HashMap<CellIndex, CellState>,
+    ruleset: RuleSet,
+    // Cells that transitioned Dead -> Alive / Alive -> Dead on the most recent `tick`, used by
+    // `render_delta` to avoid re-serializing the whole grid every frame.
+    births: Vec<CellIndex>,
+    deaths: Vec<CellIndex>,
+    viewport: Option<Viewport>,
 }
 
 #[wasm_bindgen]
 impl Universe {
-    pub fn new(num_init: usize, resolution: u8) -> Self {
+    /**
+     * Scatters `num_init` initial cells using the chosen `GeoSampler`. `mask_geojson` is only
+     * consulted (and required) when `sampler` is `SamplerKind::Mask`, in which case it holds the
+     * GeoJSON boundary that seeded coordinates must fall within.
+     */
+    pub fn new(num_init: usize, resolution: u8, sampler: SamplerKind, mask_geojson: Option<String>) -> Self {
         let h3_resolution = unsafe { Resolution::try_from(resolution).unwrap_unchecked() };
         let mut rng = rand::thread_rng();
-        let coords = (0..num_init).map(|_| UniformSampler::sample_coord(&mut rng));
-        let cells = coords.map(|c| c.to_cell(h3_resolution)).collect::<Vec<_>>();
+        let coords: Vec<LatLng> = match sampler {
+            SamplerKind::Uniform => {
+                (0..num_init).map(|_| UniformSampler.sample_coord(&mut rng)).collect()
+            }
+            SamplerKind::Cluster => {
+                let cluster_sampler = ClusterSampler::new(&mut rng, DEFAULT_NUM_CLUSTERS, DEFAULT_CLUSTER_RADIUS);
+                (0..num_init).map(|_| cluster_sampler.sample_coord(&mut rng)).collect()
+            }
+            SamplerKind::Mask => {
+                let mask_geojson = mask_geojson.expect("SamplerKind::Mask requires mask_geojson");
+                let mask_sampler = MaskSampler::from_geojson(&mask_geojson);
+                (0..num_init).map(|_| mask_sampler.sample_coord(&mut rng)).collect()
+            }
+        };
+        let cells = coords.into_iter().map(|c| c.to_cell(h3_resolution)).collect::<Vec<_>>();
         let mut cells_map = HashMap::new();
         for cell in cells {
             cells_map.insert(cell, CellState::Alive);
         }
-        Universe { 
-            cells: cells_map, 
+        Universe {
+            cells: cells_map,
+            ruleset: RuleSet::default(),
+            births: Vec::new(),
+            deaths: Vec::new(),
+            viewport: None,
+        }
+    }
+
+    /**
+     * Replaces the birth/survival ruleset used by `tick`.
+     */
+    pub fn set_ruleset(&mut self, birth: Vec<u8>, survival: Vec<u8>) {
+        self.ruleset = RuleSet { birth, survival };
+    }
+
+    /**
+     * Seeds the Universe by filling every H3 cell covered by the boundary polygons of a GeoJSON
+     * `FeatureCollection` (or a single `Feature`/`Geometry`). Only `Polygon`/`MultiPolygon`
+     * geometries are rasterized; other geometry types are skipped.
+     */
+    pub fn from_geojson(geojson: &str, resolution: u8) -> Self {
+        let h3_resolution = unsafe { Resolution::try_from(resolution).unwrap_unchecked() };
+        let config = PolyfillConfig::new(h3_resolution);
+
+        let mut cells_map = HashMap::new();
+        for geometry in parse_geojson_geometries(geojson) {
+            if !matches!(geometry, GeoGeometry::Polygon(_) | GeoGeometry::MultiPolygon(_)) {
+                continue;
+            }
+            let h3_geometry = H3Geometry::from_degrees(geometry).expect("invalid polygon geometry");
+            for cell in h3_geometry.to_cells(config) {
+                cells_map.insert(cell, CellState::Alive);
+            }
+        }
+
+        Universe {
+            cells: cells_map,
+            ruleset: RuleSet::default(),
+            births: Vec::new(),
+            deaths: Vec::new(),
+            viewport: None,
         }
     }
 
+    /**
+     * Restricts `tick`/`render` to cells whose boundary intersects the given lat/lng rectangle.
+     */
+    pub fn set_viewport(&mut self, min_lat: f64, min_lng: f64, max_lat: f64, max_lng: f64) {
+        self.viewport = Some(Viewport { min_lat, min_lng, max_lat, max_lng });
+    }
+
+    /**
+     * The cells `tick`/`render` should actually do work on: every cell when no viewport is set,
+     * or otherwise the cells of a `grid_disk` around the viewport's center cell whose boundary
+     * intersects the viewport rectangle. The disk is sized directly from the viewport's angular
+     * extent at the population's resolution (see `Viewport::search_radius`), rather than probed
+     * ring by ring against a fixed cap. Cost tracks the size of that disk, which circumscribes the
+     * viewport and so grows with its extent, not with the total population.
+     */
+    fn visible_cells(&self) -> Vec<CellIndex> {
+        let Some(viewport) = &self.viewport else {
+            return self.cells.keys().copied().collect();
+        };
+        let Some(&any_cell) = self.cells.keys().next() else {
+            return Vec::new();
+        };
+        let resolution = any_cell.resolution();
+        let center = viewport.center().to_cell(resolution);
+        let radius = viewport.search_radius(resolution);
+
+        center
+            .grid_disk::<Vec<_>>(radius)
+            .into_iter()
+            .filter(|index| self.cells.contains_key(index))
+            .filter(|index| viewport.intersects_cell(index))
+            .collect()
+    }
+
     pub fn render(&mut self) -> String {
-        let mut features: Vec<Feature> = Vec::new();
+        let visible = self.visible_cells();
+        let mut features: Vec<Feature> = Vec::with_capacity(visible.len());
         let mut tombstones: Vec<CellIndex> = Vec::new();
-        for (&index, &state) in self.cells.iter() {
-            if state == CellState::Dead {
-                tombstones.push(index);
-                continue;
+        for index in visible {
+            match self.cells.get(&index) {
+                Some(CellState::Dead) => tombstones.push(index),
+                Some(CellState::Alive) => features.push(to_feature(Index(index).into())),
+                None => {}
             }
-            features.push(to_feature(Index(index).into()));
         }
-        tombstones.into_iter().for_each(|index| {
+        for index in tombstones {
             self.cells.remove(&index);
-        });
+        }
 
         let geojson = GeoJson::FeatureCollection(FeatureCollection {
             features,
@@ -62,43 +269,131 @@ impl Universe {
     }
 
     pub fn tick(&mut self) {
-        let mut next = self.cells.clone();
-        for (index, &state) in self.cells.iter() {
-            let neighbors = index
-                .grid_disk::<Vec<_>>(1)
-                .into_iter()
-                .filter(|n| n != index)
-                .collect::<Vec<_>>();
-
-            // Cell reproduction.
-            // Handle separately because dead cells are not stored in the map.
-            for neighbor in &neighbors {
-                if self.cells.contains_key(neighbor) {
-                    continue;
-                }
-                let nc = self.live_neighbor_count(neighbor);
-                if nc == 2 {
-                    next.insert(*neighbor, CellState::Alive);
+        self.births.clear();
+        self.deaths.clear();
+
+        let active = self.visible_cells();
+
+        // Dead cells bordering at least one live cell. Collected into a set first so that each
+        // candidate (which may be in the grid_disk(1) ring of several live cells, including
+        // pentagons with only five neighbors) is only ever evaluated once.
+        let mut birth_candidates: HashSet<CellIndex> = HashSet::new();
+        for &index in &active {
+            if self.cells.get(&index) != Some(&CellState::Alive) {
+                continue;
+            }
+            for neighbor in index.grid_disk::<Vec<_>>(1).into_iter().filter(|n| *n != index) {
+                if self.cells.get(&neighbor) != Some(&CellState::Alive) {
+                    birth_candidates.insert(neighbor);
                 }
             }
+        }
+
+        // Collected rather than applied in place, since `live_neighbor_count` below must keep
+        // reading the pre-tick snapshot for the remainder of this tick.
+        let mut updates: Vec<(CellIndex, CellState)> = Vec::new();
+        for candidate in birth_candidates {
+            let nc = self.live_neighbor_count(&candidate);
+            if self.ruleset.birth.contains(&nc) {
+                updates.push((candidate, CellState::Alive));
+                self.births.push(candidate);
+            }
+        }
 
-            let num_live_neighbors = self.live_neighbor_count(index);
-            let next_state = match (state, num_live_neighbors) {
-                (CellState::Alive, x) if x < 2 => CellState::Dead,
-                (CellState::Alive, 2) | (CellState::Alive, 3) => CellState::Alive,
-                (CellState::Alive, x) if x > 3 => CellState::Dead,
-                (otherwise, _) => otherwise,
+        for &index in &active {
+            let state = self.cells[&index];
+            let num_live_neighbors = self.live_neighbor_count(&index);
+            let next_state = match state {
+                CellState::Alive if self.ruleset.survival.contains(&num_live_neighbors) => CellState::Alive,
+                CellState::Alive => CellState::Dead,
+                otherwise => otherwise,
             };
 
-            next.insert(*index, next_state);
+            if state == CellState::Alive && next_state == CellState::Dead {
+                self.deaths.push(index);
+            }
+            if next_state != state {
+                updates.push((index, next_state));
+            }
+        }
+
+        for (index, state) in updates {
+            self.cells.insert(index, state);
         }
-        self.cells = next;
+    }
+
+    /**
+     * Renders only the cells that transitioned on the most recent `tick`, as a `FeatureCollection`
+     * where each `Feature` is tagged `{"op":"add"}` or `{"op":"remove"}` (and `id`'d by the H3
+     * cell's string index) so a front-end can patch its map layer incrementally instead of
+     * reloading the whole grid every frame.
+     */
+    pub fn render_delta(&mut self) -> String {
+        let mut features: Vec<Feature> = Vec::with_capacity(self.births.len() + self.deaths.len());
+        for &index in &self.births {
+            features.push(to_delta_feature(index, "add"));
+        }
+        for &index in &self.deaths {
+            features.push(to_delta_feature(index, "remove"));
+            self.cells.remove(&index);
+        }
+
+        let geojson = GeoJson::FeatureCollection(FeatureCollection {
+            features,
+            bbox: None,
+            foreign_members: None,
+        });
+        geojson.to_string()
+    }
+
+    /**
+     * Renders a level-of-detail view for zoomed-out viewing: every live cell is rolled up to its
+     * ancestor at `target` resolution, and one hexagon is emitted per ancestor whose alive-child
+     * count is at least `DENSITY_THRESHOLD`, tagged with that count as a `density` property. `target`
+     * must be coarser than (or equal to) the resolution cells were seeded at.
+     */
+    pub fn render_at_resolution(&mut self, target: u8) -> String {
+        let target_resolution = unsafe { Resolution::try_from(target).unwrap_unchecked() };
+
+        let mut densities: HashMap<CellIndex, u32> = HashMap::new();
+        for (&index, &state) in self.cells.iter() {
+            if state != CellState::Alive {
+                continue;
+            }
+            if let Some(parent) = index.parent(target_resolution) {
+                *densities.entry(parent).or_insert(0) += 1;
+            }
+        }
+
+        let mut features: Vec<Feature> = Vec::new();
+        for (parent, density) in densities {
+            if density < DENSITY_THRESHOLD {
+                continue;
+            }
+            let mut properties = Map::new();
+            properties.insert("density".to_string(), JsonValue::from(density));
+            features.push(Feature {
+                geometry: Some(Index(parent).into()),
+                bbox: None,
+                id: Some(Id::String(parent.to_string())),
+                properties: Some(properties),
+                foreign_members: None,
+            });
+        }
+
+        let geojson = GeoJson::FeatureCollection(FeatureCollection {
+            features,
+            bbox: None,
+            foreign_members: None,
+        });
+        geojson.to_string()
     }
 
     fn live_neighbor_count(&self, index: &CellIndex) -> u8 {
         let neighbors = index.grid_disk::<Vec<_>>(1);
         neighbors
             .iter()
+            .filter(|c| *c != index)
             .filter(|c| match self.cells.get(c) {
                 Some(&state) => state == CellState::Alive,
                 None => false,
@@ -144,6 +439,25 @@ impl Into<Geometry> for Index {
     }
 }
 
+/**
+ * Parses a GeoJSON document (`FeatureCollection`, `Feature`, or bare `Geometry`) into its
+ * constituent `geo` geometries, discarding features with no geometry.
+ */
+fn parse_geojson_geometries(geojson: &str) -> Vec<GeoGeometry<f64>> {
+    let parsed: GeoJson = geojson.parse().expect("invalid GeoJSON input");
+    let features = match parsed {
+        GeoJson::FeatureCollection(fc) => fc.features,
+        GeoJson::Feature(feature) => vec![feature],
+        GeoJson::Geometry(geometry) => vec![to_feature(geometry)],
+    };
+
+    features
+        .into_iter()
+        .filter_map(|feature| feature.geometry)
+        .map(|geometry| GeoGeometry::try_from(geometry).expect("unsupported geometry type"))
+        .collect()
+}
+
 fn to_feature(geom: Geometry) -> Feature {
     Feature{
         geometry: Some(geom),
@@ -153,26 +467,190 @@ fn to_feature(geom: Geometry) -> Feature {
         foreign_members: None,
     }
 }
+
+fn to_delta_feature(index: CellIndex, op: &str) -> Feature {
+    let mut properties = Map::new();
+    properties.insert("op".to_string(), JsonValue::String(op.to_string()));
+    Feature {
+        geometry: Some(Index(index).into()),
+        bbox: None,
+        id: Some(Id::String(index.to_string())),
+        properties: Some(properties),
+        foreign_members: None,
+    }
+}
     
 const MIN_LAT: f64 = -90.0;
 const MAX_LAT: f64 = 90.0;
 const MIN_LNG: f64 = -180.0;
 const MAX_LNG: f64 = 180.0;
 
+// Minimum number of alive descendants an ancestor cell must have to be drawn in
+// `render_at_resolution`; filters out near-empty parents that would just add noise at low zoom.
+const DENSITY_THRESHOLD: u32 = 2;
+
 pub fn create_rng(seed: u64) -> StdRng {
     StdRng::seed_from_u64(seed)
 }
 
 pub trait GeoSampler<R> {
-    fn sample_coord(rng: &mut R) -> LatLng;
+    fn sample_coord(&self, rng: &mut R) -> LatLng;
 }
 
 pub struct UniformSampler;
 impl<R: Rng> GeoSampler<R> for UniformSampler {
-    fn sample_coord(rng: &mut R) -> LatLng {
+    fn sample_coord(&self, rng: &mut R) -> LatLng {
         let dist_lat = Uniform::new(MIN_LAT, MAX_LAT);
         let dist_lng = Uniform::new(MIN_LNG, MAX_LNG);
         unsafe { LatLng::new(dist_lng.sample(rng), dist_lat.sample(rng)).unwrap_unchecked() }
     }
 }
 
+// Resolution at which cluster offsets are sampled via `grid_disk`; independent of the resolution
+// cells are ultimately seeded at.
+const CLUSTER_SAMPLE_RESOLUTION: u8 = 5;
+const DEFAULT_NUM_CLUSTERS: usize = 5;
+const DEFAULT_CLUSTER_RADIUS: u32 = 3;
+
+/**
+ * Picks a handful of random seed centers up front, then samples each coordinate as the center of
+ * a random cell within a `grid_disk` radius around a randomly chosen center. Centers and disk are
+ * taken at `CLUSTER_SAMPLE_RESOLUTION`, so when `Universe::new` seeds at that resolution (or a
+ * coarser one) it produces interacting colonies instead of uniformly scattered, mostly
+ * non-adjacent points. At finer seeding resolutions each seeded cell sits at the center of a
+ * different resolution-5 cell, and cells of the same cluster are not adjacent to one another.
+ */
+pub struct ClusterSampler {
+    centers: Vec<CellIndex>,
+    radius: u32,
+}
+
+impl ClusterSampler {
+    pub fn new<R: Rng>(rng: &mut R, num_clusters: usize, radius: u32) -> Self {
+        let sample_resolution = unsafe { Resolution::try_from(CLUSTER_SAMPLE_RESOLUTION).unwrap_unchecked() };
+        let centers = (0..num_clusters)
+            .map(|_| UniformSampler.sample_coord(rng).to_cell(sample_resolution))
+            .collect();
+        ClusterSampler { centers, radius }
+    }
+}
+
+impl<R: Rng> GeoSampler<R> for ClusterSampler {
+    fn sample_coord(&self, rng: &mut R) -> LatLng {
+        let center = self.centers[rng.gen_range(0..self.centers.len())];
+        let disk = center.grid_disk::<Vec<_>>(self.radius);
+        disk[rng.gen_range(0..disk.len())].into()
+    }
+}
+
+/**
+ * Holds a `geo` mask polygon and performs rejection sampling, redrawing uniform coordinates until
+ * one lands inside the mask. Lets callers confine seeding to a continent, country, or other
+ * region outline.
+ */
+pub struct MaskSampler {
+    mask: MultiPolygon<f64>,
+}
+
+impl MaskSampler {
+    /**
+     * Builds a mask from the `Polygon`/`MultiPolygon` geometries of a GeoJSON document; other
+     * geometry types are ignored.
+     *
+     * Panics if none of the document's geometries are a `Polygon`/`MultiPolygon`, since
+     * `sample_coord`'s rejection sampling would otherwise loop forever against an empty mask.
+     */
+    pub fn from_geojson(geojson: &str) -> Self {
+        let polygons = parse_geojson_geometries(geojson)
+            .into_iter()
+            .flat_map(|geometry| match geometry {
+                GeoGeometry::Polygon(polygon) => vec![polygon],
+                GeoGeometry::MultiPolygon(multi_polygon) => multi_polygon.0,
+                _ => Vec::new(),
+            })
+            .collect::<Vec<_>>();
+        assert!(!polygons.is_empty(), "MaskSampler requires at least one Polygon/MultiPolygon geometry");
+        MaskSampler { mask: MultiPolygon(polygons) }
+    }
+}
+
+impl<R: Rng> GeoSampler<R> for MaskSampler {
+    fn sample_coord(&self, rng: &mut R) -> LatLng {
+        let dist_lat = Uniform::new(MIN_LAT, MAX_LAT);
+        let dist_lng = Uniform::new(MIN_LNG, MAX_LNG);
+        loop {
+            let (lat, lng) = (dist_lat.sample(rng), dist_lng.sample(rng));
+            let point = Point::new(lng, lat);
+            if self.mask.contains(&point) {
+                return unsafe { LatLng::new(lat, lng).unwrap_unchecked() };
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn universe_with(cells: HashMap<CellIndex, CellState>) -> Universe {
+        Universe {
+            cells,
+            ruleset: RuleSet::default(),
+            births: Vec::new(),
+            deaths: Vec::new(),
+            viewport: None,
+        }
+    }
+
+    fn center_and_real_neighbors() -> (CellIndex, Vec<CellIndex>) {
+        let resolution = Resolution::try_from(5).unwrap();
+        let center = LatLng::new(0.0, 0.0).unwrap().to_cell(resolution);
+        let neighbors = center
+            .grid_disk::<Vec<_>>(1)
+            .into_iter()
+            .filter(|c| *c != center)
+            .collect();
+        (center, neighbors)
+    }
+
+    #[test]
+    fn live_neighbor_count_excludes_the_cell_itself() {
+        let (center, neighbors) = center_and_real_neighbors();
+        let mut cells = HashMap::new();
+        cells.insert(center, CellState::Alive);
+        cells.insert(neighbors[0], CellState::Alive);
+        cells.insert(neighbors[1], CellState::Alive);
+        let universe = universe_with(cells);
+
+        assert_eq!(universe.live_neighbor_count(&center), 2);
+    }
+
+    #[test]
+    fn tick_kills_a_live_cell_with_only_one_real_neighbor() {
+        let (center, neighbors) = center_and_real_neighbors();
+        let mut cells = HashMap::new();
+        cells.insert(center, CellState::Alive);
+        cells.insert(neighbors[0], CellState::Alive);
+        let mut universe = universe_with(cells);
+
+        universe.tick();
+
+        assert_eq!(universe.cells.get(&center), Some(&CellState::Dead));
+    }
+
+    #[test]
+    fn tick_keeps_a_live_cell_with_three_real_neighbors_alive() {
+        let (center, neighbors) = center_and_real_neighbors();
+        let mut cells = HashMap::new();
+        cells.insert(center, CellState::Alive);
+        cells.insert(neighbors[0], CellState::Alive);
+        cells.insert(neighbors[1], CellState::Alive);
+        cells.insert(neighbors[2], CellState::Alive);
+        let mut universe = universe_with(cells);
+
+        universe.tick();
+
+        assert_eq!(universe.cells.get(&center), Some(&CellState::Alive));
+    }
+}
+